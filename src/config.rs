@@ -0,0 +1,182 @@
+use std::{io, path::Path};
+
+use glam::IVec3;
+use glob::Pattern;
+use half::f16;
+use serde::Deserialize;
+
+use crate::sinks::VoxelValue;
+
+/// Per-run settings loaded from `--config <file.toml>`: which grids to
+/// export, what type to decode each one as, and an optional crop region.
+/// An empty `grids` list (the default with no `--config`) keeps the
+/// original behavior of exporting every grid as `f16` with no cropping.
+#[derive(Debug, Deserialize, Default, Hash)]
+pub struct Config {
+    #[serde(default)]
+    pub grids: Vec<GridConfig>,
+}
+
+#[derive(Debug, Deserialize, Hash)]
+pub struct GridConfig {
+    /// Grid name, or a glob pattern such as `"temp_*"`.
+    pub name: String,
+    #[serde(default)]
+    pub voxel_type: VoxelType,
+    pub bbox: Option<BBox>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VoxelType {
+    #[default]
+    F16,
+    F32,
+    U32,
+}
+
+/// Inclusive axis-aligned bounding box voxels are cropped to before
+/// they're handed to the output sink.
+#[derive(Debug, Deserialize, Clone, Copy, Hash)]
+pub struct BBox {
+    pub min: [i32; 3],
+    pub max: [i32; 3],
+}
+
+impl BBox {
+    pub fn contains(&self, pos: IVec3) -> bool {
+        (self.min[0]..=self.max[0]).contains(&pos.x)
+            && (self.min[1]..=self.max[1]).contains(&pos.y)
+            && (self.min[2]..=self.max[2]).contains(&pos.z)
+    }
+}
+
+/// Resolved settings for exporting a single grid.
+pub struct GridSettings {
+    pub voxel_type: VoxelType,
+    pub bbox: Option<BBox>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> io::Result<Config> {
+        let text = std::fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(io::Error::other)
+    }
+
+    /// Settings for `grid_name`, or `None` if it should be skipped
+    /// because a non-empty `grids` list doesn't mention it.
+    pub fn grid_settings(&self, grid_name: &str) -> Option<GridSettings> {
+        if self.grids.is_empty() {
+            return Some(GridSettings {
+                voxel_type: VoxelType::F16,
+                bbox: None,
+            });
+        }
+        self.grids
+            .iter()
+            .find(|grid| glob_match(&grid.name, grid_name))
+            .map(|grid| GridSettings {
+                voxel_type: grid.voxel_type,
+                bbox: grid.bbox,
+            })
+    }
+
+    /// A short hash identifying these settings, so a resumable batch run
+    /// (see `manifest`) can tell whether a source was last converted
+    /// under a different `--config` and needs reconverting.
+    pub fn fingerprint(&self) -> String {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.grids.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+    Pattern::new(pattern).is_ok_and(|pattern| pattern.matches(name))
+}
+
+/// Wraps a decoded voxel value in a `VoxelValue` tagged with its actual
+/// `VoxelType`, so a sink can carry it through at full precision instead
+/// of narrowing everything to `f16` (which silently turns a `u32` like
+/// `100_000` into `inf`).
+pub trait IntoVoxelValue {
+    fn into_voxel_value(self) -> VoxelValue;
+}
+
+impl IntoVoxelValue for f16 {
+    fn into_voxel_value(self) -> VoxelValue {
+        VoxelValue::F16(self)
+    }
+}
+
+impl IntoVoxelValue for f32 {
+    fn into_voxel_value(self) -> VoxelValue {
+        VoxelValue::F32(self)
+    }
+}
+
+impl IntoVoxelValue for u32 {
+    fn into_voxel_value(self) -> VoxelValue {
+        VoxelValue::U32(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bbox_contains_is_inclusive_of_both_bounds() {
+        let bbox = BBox {
+            min: [0, 0, 0],
+            max: [10, 10, 10],
+        };
+        assert!(bbox.contains(IVec3::new(0, 0, 0)));
+        assert!(bbox.contains(IVec3::new(10, 10, 10)));
+        assert!(bbox.contains(IVec3::new(5, 5, 5)));
+        assert!(!bbox.contains(IVec3::new(-1, 5, 5)));
+        assert!(!bbox.contains(IVec3::new(5, 11, 5)));
+    }
+
+    #[test]
+    fn grid_settings_defaults_to_f16_with_no_crop_when_unconfigured() {
+        let config = Config::default();
+        let settings = config.grid_settings("anything").unwrap();
+        assert!(matches!(settings.voxel_type, VoxelType::F16));
+        assert!(settings.bbox.is_none());
+    }
+
+    #[test]
+    fn grid_settings_matches_glob_pattern_and_skips_unlisted_grids() {
+        let config = Config {
+            grids: vec![GridConfig {
+                name: "temp_*".to_string(),
+                voxel_type: VoxelType::U32,
+                bbox: None,
+            }],
+        };
+        let matched = config.grid_settings("temp_outside").unwrap();
+        assert!(matches!(matched.voxel_type, VoxelType::U32));
+        assert!(config.grid_settings("density").is_none());
+    }
+
+    #[test]
+    fn fingerprint_changes_when_grid_settings_change() {
+        let a = Config {
+            grids: vec![GridConfig {
+                name: "density".to_string(),
+                voxel_type: VoxelType::F16,
+                bbox: None,
+            }],
+        };
+        let b = Config {
+            grids: vec![GridConfig {
+                name: "density".to_string(),
+                voxel_type: VoxelType::U32,
+                bbox: None,
+            }],
+        };
+        assert_ne!(a.fingerprint(), b.fingerprint());
+    }
+}
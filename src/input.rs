@@ -0,0 +1,170 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::bufread::GzDecoder;
+use log::warn;
+use zip::ZipArchive;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+/// Compression a source file may be wrapped in.
+#[derive(Clone, Copy, Debug)]
+pub enum Codec {
+    Gzip,
+    Zstd,
+}
+
+/// One discoverable `.vdb` source: a plain or compressed file on disk, or
+/// a member inside a `.zip` bundle. `read` always yields a fully decoded
+/// in-memory buffer so callers can wrap it in a `Cursor`, which is both
+/// `Read` and `Seek` regardless of whether the original file was.
+pub enum VdbSource {
+    File(PathBuf),
+    CompressedFile(PathBuf, Codec),
+    ZipMember { archive: PathBuf, member: String },
+}
+
+impl VdbSource {
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        match self {
+            VdbSource::File(path) => std::fs::read(path),
+            VdbSource::CompressedFile(path, codec) => {
+                let reader = BufReader::new(File::open(path)?);
+                let mut buf = Vec::new();
+                match codec {
+                    Codec::Gzip => GzDecoder::new(reader).read_to_end(&mut buf)?,
+                    Codec::Zstd => ZstdDecoder::new(reader)?.read_to_end(&mut buf)?,
+                };
+                Ok(buf)
+            }
+            VdbSource::ZipMember { archive, member } => {
+                let mut zip = ZipArchive::new(File::open(archive)?).map_err(io::Error::other)?;
+                let mut entry = zip.by_name(member).map_err(io::Error::other)?;
+                let mut buf = Vec::new();
+                entry.read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+        }
+    }
+
+    /// The real on-disk file backing this source (the archive itself for
+    /// a zip member), for stat-based freshness checks.
+    pub fn disk_path(&self) -> &Path {
+        match self {
+            VdbSource::File(path) | VdbSource::CompressedFile(path, _) => path,
+            VdbSource::ZipMember { archive, .. } => archive,
+        }
+    }
+
+    /// Output path for this source relative to `source_dir`, with any
+    /// compression suffix stripped and, for zip bundles, the member name
+    /// embedded so multiple archives don't collide.
+    pub fn output_subpath(&self, source_dir: &Path) -> Option<PathBuf> {
+        match self {
+            VdbSource::File(path) => strip_prefix(path, source_dir),
+            VdbSource::CompressedFile(path, _) => {
+                strip_prefix(path, source_dir).map(|local| strip_compression_suffix(&local))
+            }
+            VdbSource::ZipMember { archive, member } => {
+                strip_prefix(archive, source_dir).map(|local| local.with_extension("").join(member))
+            }
+        }
+    }
+}
+
+fn strip_prefix(path: &Path, source_dir: &Path) -> Option<PathBuf> {
+    path.strip_prefix(source_dir).ok().map(Path::to_path_buf)
+}
+
+fn strip_compression_suffix(path: &Path) -> PathBuf {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") | Some("zst") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn has_vdb_stem(path: &Path) -> bool {
+    path.file_stem()
+        .map(Path::new)
+        .and_then(|stem| stem.extension())
+        .is_some_and(|ext| ext == "vdb")
+}
+
+/// Classifies a walked path as zero or more `VdbSource`s: a plain `.vdb`
+/// file, a `.vdb.gz`/`.vdb.zst` archive, or the `.vdb` members of a
+/// `.zip` bundle. Anything else yields no sources.
+pub fn discover_sources(path: &Path) -> Vec<VdbSource> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("vdb") => vec![VdbSource::File(path.to_path_buf())],
+        Some("gz") if has_vdb_stem(path) => {
+            vec![VdbSource::CompressedFile(path.to_path_buf(), Codec::Gzip)]
+        }
+        Some("zst") if has_vdb_stem(path) => {
+            vec![VdbSource::CompressedFile(path.to_path_buf(), Codec::Zstd)]
+        }
+        Some("zip") => zip_vdb_members(path),
+        _ => Vec::new(),
+    }
+}
+
+fn zip_vdb_members(path: &Path) -> Vec<VdbSource> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let Ok(mut archive) = ZipArchive::new(file) else {
+        return Vec::new();
+    };
+    (0..archive.len())
+        .filter_map(|i| {
+            let entry = archive.by_index(i).ok()?;
+            let name = entry.name().to_string();
+            if Path::new(&name).extension() != Some(std::ffi::OsStr::new("vdb")) {
+                return None;
+            }
+            if !is_safe_member_name(&name) {
+                warn!(
+                    "Skipping zip member {:?} in {:?}: escapes the archive's own directory",
+                    name, path
+                );
+                return None;
+            }
+            Some(VdbSource::ZipMember {
+                archive: path.to_path_buf(),
+                member: name,
+            })
+        })
+        .collect()
+}
+
+/// Rejects zip member names that could walk the eventual output path
+/// outside the output directory (`..` components, or an absolute path),
+/// a classic zip-slip vector for archives pulled in from outside sources.
+fn is_safe_member_name(name: &str) -> bool {
+    Path::new(name)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod is_safe_member_name_tests {
+    use super::*;
+
+    #[test]
+    fn accepts_plain_relative_names() {
+        assert!(is_safe_member_name("density.vdb"));
+        assert!(is_safe_member_name("sub/dir/density.vdb"));
+    }
+
+    #[test]
+    fn rejects_parent_dir_traversal() {
+        assert!(!is_safe_member_name("../../../etc/cron.d/x.vdb"));
+        assert!(!is_safe_member_name("sub/../../x.vdb"));
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        assert!(!is_safe_member_name("/etc/cron.d/x.vdb"));
+    }
+}
@@ -1,18 +1,34 @@
 use std::{
     collections::HashMap,
-    fs::File,
-    io::{BufReader, Write},
-    path::Path,
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
-use clap::Parser;
-use csv::Writer;
-use glam::{ivec3, IVec3, Vec3};
+use bytemuck::Pod;
+use clap::{Parser, ValueEnum};
+use config::{Config, IntoVoxelValue, VoxelType};
+use glam::ivec3;
 use half::f16;
-use log::{error, info, LevelFilter};
+use input::VdbSource;
+use log::{error, info, warn, LevelFilter};
+use manifest::Manifest;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use sinks::{BinSink, CsvSink, NdjsonSink, ParquetSink, VoxelSink};
 use vdb_rs::VdbReader;
 use walkdir::WalkDir;
 
+mod config;
+mod input;
+mod manifest;
+mod sinks;
+mod text_to_vdb;
+
+/// How long a path must go without a further filesystem event before we
+/// treat it as settled and safe to convert (avoids reading half-written files).
+const WATCH_SETTLE_WINDOW: Duration = Duration::from_millis(500);
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
@@ -24,69 +40,545 @@ struct Args {
     recursive: bool,
     #[arg(short, long)]
     multithreading: bool,
+    /// Keep running and convert `.vdb` files as they are created or modified.
+    #[arg(short, long)]
+    watch: bool,
+    /// Output format for converted voxel records.
+    #[arg(short, long, value_enum, default_value = "csv")]
+    format: OutputFormat,
+    /// Reverse mode: merge previously exported CSVs in `source_directory`
+    /// back into one file per source `.vdb`, instead of converting `.vdb` to
+    /// CSV. Only the `--format csv` output can be reversed; `json`/`bin`/
+    /// `parquet` exports are not read back by this mode. This does not
+    /// produce an OpenVDB grid -- `vdb-rs` has no grid-writing API -- so
+    /// the merged output is a plain-text dump of the validated records,
+    /// not a real `.vdb` file.
+    #[arg(long)]
+    reverse: bool,
+    /// Optional TOML config selecting which grids to export, their voxel
+    /// decode type, and a crop region. Grids not listed are skipped.
+    #[arg(short, long)]
+    config: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    Csv,
+    Json,
+    Bin,
+    Parquet,
+}
+
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "jsonl",
+            OutputFormat::Bin => "bin",
+            OutputFormat::Parquet => "parquet",
+        }
+    }
+
+    fn create_sink(self, path: &Path) -> std::io::Result<Box<dyn VoxelSink>> {
+        match self {
+            OutputFormat::Csv => Ok(Box::new(CsvSink::create(path)?)),
+            OutputFormat::Json => Ok(Box::new(NdjsonSink::create(path)?)),
+            OutputFormat::Bin => Ok(Box::new(BinSink::create(path)?)),
+            OutputFormat::Parquet => Ok(Box::new(ParquetSink::create(path)?)),
+        }
+    }
+}
+
+/// Runs a long-lived daemon loop that watches `source_dir` for `.vdb` files
+/// being created or modified and converts them as they settle, instead of
+/// doing a single `WalkDir` pass.
+fn run_watch(
+    source_dir: &Path,
+    output_dir: &Path,
+    recursive: bool,
+    format: OutputFormat,
+    config: &Config,
+) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            error!("Failed to create filesystem watcher: {}", err);
+            return;
+        }
+    };
+
+    let recursive_mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    if let Err(err) = watcher.watch(source_dir, recursive_mode) {
+        error!("Failed to watch {:?}: {}", source_dir, err);
+        return;
+    }
+
+    info!("Watching {:?} for .vdb changes", source_dir);
+
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    loop {
+        match rx.recv_timeout(WATCH_SETTLE_WINDOW) {
+            Ok(Ok(event)) => record_vdb_event(&mut pending, event),
+            Ok(Err(err)) => error!("Watch error: {}", err),
+            Err(_) => (), // timeout: fall through and flush anything that has settled
+        }
+        dispatch_settled(&mut pending, source_dir, output_dir, format, config);
+    }
+}
+
+fn record_vdb_event(pending: &mut HashMap<PathBuf, Instant>, event: Event) {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return;
+    }
+    for path in event.paths {
+        if !input::discover_sources(&path).is_empty() {
+            pending.insert(path, Instant::now());
+        }
+    }
+}
+
+fn dispatch_settled(
+    pending: &mut HashMap<PathBuf, Instant>,
+    source_dir: &Path,
+    output_dir: &Path,
+    format: OutputFormat,
+    config: &Config,
+) {
+    let now = Instant::now();
+    let settled: Vec<PathBuf> = pending
+        .iter()
+        .filter(|(_, &last_seen)| now.duration_since(last_seen) >= WATCH_SETTLE_WINDOW)
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    for path in settled {
+        pending.remove(&path);
+        for source in input::discover_sources(&path) {
+            let Some(local_pos) = source.output_subpath(source_dir) else {
+                continue;
+            };
+            let output_path = output_dir.join(local_pos);
+            if is_output_up_to_date(&path, &output_path, format) {
+                continue;
+            }
+            convert_vdb_file(&source, &path, &output_path, format, config);
+        }
+    }
 }
 
-fn parse_vdb_file(source_path: &Path, output_path: &Path) {
+#[cfg(test)]
+mod watch_debounce_tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind};
+
+    #[test]
+    fn ignores_events_that_are_not_create_or_modify() {
+        let mut pending = HashMap::new();
+        let path = PathBuf::from("foo.vdb");
+        let event = Event::new(EventKind::Access(AccessKind::Any)).add_path(path);
+
+        record_vdb_event(&mut pending, event);
+
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn ignores_paths_with_no_vdb_sources() {
+        let mut pending = HashMap::new();
+        let path = PathBuf::from("foo.txt");
+        let event = Event::new(EventKind::Create(CreateKind::Any)).add_path(path);
+
+        record_vdb_event(&mut pending, event);
+
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn records_a_settling_vdb_path() {
+        let mut pending = HashMap::new();
+        let path = PathBuf::from("foo.vdb");
+        let event = Event::new(EventKind::Create(CreateKind::Any)).add_path(path.clone());
+
+        record_vdb_event(&mut pending, event);
+
+        assert!(pending.contains_key(&path));
+    }
+
+    #[test]
+    fn dispatch_settled_leaves_a_fresh_event_pending() {
+        let dir = std::env::temp_dir().join("vdb_to_text_test_watch_not_settled");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("foo.vdb");
+
+        let mut pending = HashMap::new();
+        pending.insert(path.clone(), Instant::now());
+
+        dispatch_settled(
+            &mut pending,
+            &dir,
+            &dir,
+            OutputFormat::Csv,
+            &Config::default(),
+        );
+
+        assert!(pending.contains_key(&path));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dispatch_settled_dispatches_once_the_settle_window_elapses() {
+        let dir = std::env::temp_dir().join("vdb_to_text_test_watch_settled");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("foo.vdb");
+
+        let mut pending = HashMap::new();
+        pending.insert(
+            path.clone(),
+            Instant::now() - WATCH_SETTLE_WINDOW - Duration::from_millis(50),
+        );
+
+        dispatch_settled(
+            &mut pending,
+            &dir,
+            &dir,
+            OutputFormat::Csv,
+            &Config::default(),
+        );
+
+        assert!(pending.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+/// `convert_vdb_file` never writes to `output_path` itself: it writes one
+/// sibling per grid, named `<output_path-stem>.<grid_name>.<ext>`. So
+/// "up to date" means at least one such sibling exists and every one of
+/// them is newer than the source, not that `output_path` itself exists.
+fn is_output_up_to_date(source_path: &Path, output_path: &Path, format: OutputFormat) -> bool {
+    let Ok(source_modified) = std::fs::metadata(source_path).and_then(|m| m.modified()) else {
+        return false;
+    };
+    let Some(parent) = output_path.parent() else {
+        return false;
+    };
+    let Some(stem) = output_path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
+    };
+    let Ok(siblings) = std::fs::read_dir(parent) else {
+        return false;
+    };
+
+    let prefix = format!("{}.", stem);
+    let suffix = format!(".{}", format.extension());
+    let mut found_any = false;
+    for entry in siblings.flatten() {
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if !(name.starts_with(&prefix) && name.ends_with(&suffix)) {
+            continue;
+        }
+        let Ok(output_modified) = entry.metadata().and_then(|m| m.modified()) else {
+            return false;
+        };
+        if output_modified < source_modified {
+            return false;
+        }
+        found_any = true;
+    }
+    found_any
+}
+
+#[cfg(test)]
+mod is_output_up_to_date_tests {
+    use super::*;
+
+    fn touch(path: &Path) {
+        std::fs::write(path, b"x").unwrap();
+    }
+
+    #[test]
+    fn false_when_no_grid_outputs_exist() {
+        let dir = std::env::temp_dir().join("vdb_to_text_test_no_outputs");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("foo.vdb");
+        touch(&source);
+
+        assert!(!is_output_up_to_date(
+            &source,
+            &dir.join("foo"),
+            OutputFormat::Csv
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn true_when_every_grid_output_is_newer_than_source() {
+        let dir = std::env::temp_dir().join("vdb_to_text_test_fresh_outputs");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("foo.vdb");
+        touch(&source);
+        touch(&dir.join("foo.density.csv"));
+        touch(&dir.join("foo.temperature.csv"));
+
+        assert!(is_output_up_to_date(
+            &source,
+            &dir.join("foo"),
+            OutputFormat::Csv
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn false_when_source_is_newer_than_an_existing_grid_output() {
+        let dir = std::env::temp_dir().join("vdb_to_text_test_stale_output");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        touch(&dir.join("foo.density.csv"));
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let source = dir.join("foo.vdb");
+        touch(&source);
+
+        assert!(!is_output_up_to_date(
+            &source,
+            &dir.join("foo"),
+            OutputFormat::Csv
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+/// Core conversion: decodes `source` and writes one sink per configured
+/// grid under `output_path`. Returns whether the conversion succeeded;
+/// the batch path's `parse_vdb_file` wrapper uses that to decide whether
+/// to record the manifest entry, `--watch` has no manifest and ignores it.
+fn convert_vdb_file(
+    source: &VdbSource,
+    source_label: &Path,
+    output_path: &Path,
+    format: OutputFormat,
+    config: &Config,
+) -> bool {
     // Make sure the output dir exists
     match output_path.parent() {
         Some(source_path) => match std::fs::create_dir_all(source_path) {
             Ok(_) => (),
             Err(err) => {
                 error!("{}", err);
-                return;
+                return false;
             }
         },
         None => {
             error!("{:?} does not have a parent directory", output_path);
-            return;
+            return false;
         }
     }
 
+    // Decompress (if necessary) into memory once; re-reading a `Cursor`
+    // per grid is cheap and, unlike a streaming decoder, supports seeking.
+    let bytes = match source.read() {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            error!("{}", err);
+            return false;
+        }
+    };
+
     // Find grid names
     let grid_names = {
-        let vdb_file = File::open(source_path).unwrap();
-        let vdb_reader = VdbReader::new(BufReader::new(&vdb_file)).unwrap();
+        let vdb_reader = match VdbReader::new(Cursor::new(&bytes)) {
+            Ok(vdb_reader) => vdb_reader,
+            Err(err) => {
+                error!("{:?} is not a valid VDB stream: {}", source_label, err);
+                return false;
+            }
+        };
         vdb_reader.available_grids()
     };
 
-    // Setup structure to collect voxel values into
-    type VoxelT = f16;
+    let mut succeeded = true;
     for (i, name) in grid_names.iter().enumerate() {
+        // Grids not listed in a non-empty config are skipped entirely.
+        let Some(settings) = config.grid_settings(name) else {
+            continue;
+        };
+
         // open reader
-        let vdb_file = File::open(source_path).unwrap();
-        let reader = BufReader::new(&vdb_file);
-        let mut vdb_reader = VdbReader::new(reader).unwrap();
-
-        // open writer
-        let csv_filename = output_path.with_extension(name.clone() + ".csv");
-        info!("{:?} => {:?}", source_path, csv_filename);
-        let mut wtr = match Writer::from_path(&csv_filename) {
-            Ok(writer) => writer,
+        let mut vdb_reader = match VdbReader::new(Cursor::new(&bytes)) {
+            Ok(vdb_reader) => vdb_reader,
+            Err(err) => {
+                error!("{:?} is not a valid VDB stream: {}", source_label, err);
+                succeeded = false;
+                continue;
+            }
+        };
+
+        // open sink
+        let sink_filename = output_path.with_extension(format!("{}.{}", name, format.extension()));
+        info!("{:?} => {:?}", source_label, sink_filename);
+        let mut sink = match format.create_sink(&sink_filename) {
+            Ok(sink) => sink,
             Err(err) => {
                 error!("{}", err);
-                let alternative_csv_filename = output_path.with_extension(i.to_string() + ".csv");
+                let alternative_filename =
+                    output_path.with_extension(format!("{}.{}", i, format.extension()));
                 error!(
                     "Grid with name: {} could not create output file with name {:?}. Resorting to file name: {:?}",
-                    name, csv_filename, alternative_csv_filename
+                    name, sink_filename, alternative_filename
                 );
-                Writer::from_path(&alternative_csv_filename).unwrap()
+                format.create_sink(&alternative_filename).unwrap()
+            }
+        };
+        if let Err(err) = sink.write_header() {
+            error!("Failed to write header for grid {:?}: {}", name, err);
+            succeeded = false;
+            continue;
+        }
+
+        let exported = match settings.voxel_type {
+            VoxelType::F16 => {
+                export_grid::<f16>(&mut vdb_reader, name, settings.bbox, sink.as_mut())
+            }
+            VoxelType::F32 => {
+                export_grid::<f32>(&mut vdb_reader, name, settings.bbox, sink.as_mut())
+            }
+            VoxelType::U32 => {
+                export_grid::<u32>(&mut vdb_reader, name, settings.bbox, sink.as_mut())
             }
         };
-        wtr.write_record(vec!["x", "y", "z", "span", "value"])
-            .unwrap();
-
-        // Read specific grid
-        let grid = vdb_reader.read_grid::<VoxelT>(name).unwrap();
-        for (position, voxel, level) in grid.iter() {
-            let position = ivec3(position.x as i32, position.y as i32, position.z as i32);
-            wtr.write_record(vec![
-                position.x.to_string(),
-                position.y.to_string(),
-                position.z.to_string(),
-                (level.scale() as i32).to_string(),
-                voxel.to_string(),
-            ])
-            .unwrap();
+        if !exported {
+            succeeded = false;
+        }
+
+        if let Err(err) = sink.finish() {
+            error!("Failed to finish writing grid {:?}: {}", name, err);
+            succeeded = false;
+        }
+    }
+
+    succeeded
+}
+
+/// Batch-path wrapper around `convert_vdb_file` that also records the
+/// conversion in the manifest so a resumed run can skip it next time.
+fn parse_vdb_file(
+    source: &VdbSource,
+    source_label: &Path,
+    output_path: &Path,
+    format: OutputFormat,
+    config: &Config,
+    manifest: &Mutex<Manifest>,
+    manifest_key: &Path,
+) {
+    if !convert_vdb_file(source, source_label, output_path, format, config) {
+        return;
+    }
+
+    if let Err(err) = manifest.lock().unwrap().record(
+        manifest_key,
+        source.disk_path(),
+        format.extension(),
+        &config.fingerprint(),
+    ) {
+        error!("Failed to update manifest for {:?}: {}", source_label, err);
+    }
+}
+
+/// Reads `name` out of `vdb_reader` as voxel type `T`, drops voxels
+/// outside `bbox` (when set), and writes the rest to `sink`. A grid
+/// stored as a different type than `--config` declares, or a sink write
+/// failure, is logged and skips the rest of this one grid rather than
+/// panicking -- under `--multithreading` this runs inside a
+/// `scope.spawn` closure, and a panic there would abort the whole batch.
+/// Returns whether every kept voxel was written.
+fn export_grid<T>(
+    vdb_reader: &mut VdbReader<Cursor<&Vec<u8>>>,
+    name: &str,
+    bbox: Option<config::BBox>,
+    sink: &mut dyn VoxelSink,
+) -> bool
+where
+    T: IntoVoxelValue + Pod,
+{
+    let grid = match vdb_reader.read_grid::<T>(name) {
+        Ok(grid) => grid,
+        Err(err) => {
+            error!(
+                "Failed to read grid {:?} as the configured voxel type: {}",
+                name, err
+            );
+            return false;
+        }
+    };
+    for (position, voxel, level) in grid.iter() {
+        let position = ivec3(position.x as i32, position.y as i32, position.z as i32);
+        if bbox.is_some_and(|bbox| !bbox.contains(position)) {
+            continue;
+        }
+        if let Err(err) = sink.write_voxel(position, level.scale() as i32, voxel.into_voxel_value())
+        {
+            error!("Failed to write a voxel for grid {:?}: {}", name, err);
+            return false;
+        }
+    }
+    true
+}
+
+/// Reverse mode: walks `source_dir` for exported CSVs, groups them back
+/// by the multi-grid source they came from, and writes each group's
+/// merged, validated records to a file under `output_dir` (see
+/// `text_to_vdb::reconstruct_vdb` for why that file isn't a real `.vdb`).
+/// Only `--format csv` output round-trips this way; a tree produced with
+/// `json`/`bin`/`parquet` has no reader here and yields an empty, silent
+/// no-op unless we warn about it below.
+fn run_reverse(source_dir: &Path, output_dir: &Path, recursive: bool) {
+    let walker = if recursive {
+        WalkDir::new(source_dir)
+    } else {
+        WalkDir::new(source_dir).max_depth(1)
+    };
+
+    let mut saw_any_entry = false;
+    let csv_paths: Vec<PathBuf> = walker
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .inspect(|_| saw_any_entry = true)
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+        .collect();
+
+    if csv_paths.is_empty() && saw_any_entry {
+        warn!(
+            "--reverse found no .csv files under {:?}; only --format csv output can be rebuilt, \
+             json/bin/parquet exports are not supported",
+            source_dir
+        );
+    }
+
+    for (base_path, members) in text_to_vdb::group_by_base_filename(csv_paths) {
+        let Ok(local_pos) = base_path.strip_prefix(source_dir) else {
+            continue;
+        };
+        let output_path = output_dir.join(local_pos);
+        if let Some(parent) = output_path.parent() {
+            if let Err(err) = std::fs::create_dir_all(parent) {
+                error!("{}", err);
+                continue;
+            }
+        }
+
+        info!("{:?} => {:?}", members, output_path);
+        if let Err(err) = text_to_vdb::reconstruct_vdb(&members, &output_path) {
+            error!("{}", err);
         }
     }
 }
@@ -104,55 +596,95 @@ fn main() {
     let source_dir = Path::new(&args.source_directory);
     let output_dir = Path::new(&args.output_directory);
 
+    if args.reverse {
+        run_reverse(source_dir, output_dir, args.recursive);
+        return;
+    }
+
+    let config = match &args.config {
+        Some(config_path) => match Config::load(Path::new(config_path)) {
+            Ok(config) => config,
+            Err(err) => {
+                error!("Failed to load config {:?}: {}", config_path, err);
+                return;
+            }
+        },
+        None => Config::default(),
+    };
+
+    if args.watch {
+        run_watch(source_dir, output_dir, args.recursive, args.format, &config);
+        return;
+    }
+
+    let manifest = match Manifest::open(output_dir) {
+        Ok(manifest) => Arc::new(Mutex::new(manifest)),
+        Err(err) => {
+            error!("Failed to open conversion manifest: {}", err);
+            return;
+        }
+    };
+
     let walker = if !args.recursive {
         WalkDir::new(source_dir).max_depth(1)
     } else {
         WalkDir::new(source_dir)
     };
 
+    let config_fingerprint = config.fingerprint();
+    // Each `--multithreading` spawn below needs its own owned handle to
+    // the shared manifest/config rather than moving the single outer
+    // one, which only the first loop iteration could do.
+    let config = Arc::new(config);
+
     rayon::scope(|scope| {
         for entry in walker {
             match entry {
                 Ok(entry) => {
-                    if let Some(file_with_extension) = entry.path().extension() {
-                        if file_with_extension == "vdb" {
-                            if args.multithreading {
-                                scope.spawn(move |_| {
-                                    let entry = entry.clone();
-
-                                    // get source dir local path
-                                    let local_pos = match entry.path().strip_prefix(&source_dir) {
-                                        Ok(local) => local,
-                                        Err(err) => {
-                                            error!("{}", err);
-                                            return;
-                                        }
-                                    };
-
-                                    // get output dir global path
-                                    let source_path = entry.path();
-                                    let output_path = output_dir.join(local_pos);
-
-                                    parse_vdb_file(source_path, &output_path);
-                                });
-                            } else {
-                                let entry = entry.clone();
-
-                                // get source dir local path
-                                let local_pos = match entry.path().strip_prefix(&source_dir) {
-                                    Ok(local) => local,
-                                    Err(err) => {
-                                        error!("{}", err);
-                                        return;
-                                    }
-                                };
-
-                                // get output dir global path
-                                let source_path = entry.path();
-                                let output_path = output_dir.join(local_pos);
-
-                                parse_vdb_file(source_path, &output_path);
-                            }
+                    for source in input::discover_sources(entry.path()) {
+                        let Some(local_pos) = source.output_subpath(source_dir) else {
+                            continue;
+                        };
+                        let output_path = output_dir.join(&local_pos);
+                        let source_label = entry.path().to_path_buf();
+
+                        if manifest.lock().unwrap().is_up_to_date(
+                            &local_pos,
+                            source.disk_path(),
+                            args.format.extension(),
+                            &config_fingerprint,
+                        ) {
+                            info!(
+                                "{:?} is unchanged since the last run, skipping",
+                                source_label
+                            );
+                            continue;
+                        }
+
+                        if args.multithreading {
+                            let config = Arc::clone(&config);
+                            let manifest = Arc::clone(&manifest);
+                            scope.spawn(move |_| {
+                                parse_vdb_file(
+                                    &source,
+                                    &source_label,
+                                    &output_path,
+                                    args.format,
+                                    &config,
+                                    &manifest,
+                                    &local_pos,
+                                );
+                            });
+                        } else {
+                            parse_vdb_file(
+                                &source,
+                                &source_label,
+                                &output_path,
+                                args.format,
+                                &config,
+                                &manifest,
+                                &local_pos,
+                            );
                         }
                     }
                 }
@@ -0,0 +1,225 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ManifestEntry {
+    key: PathBuf,
+    mtime_unix_secs: u64,
+    size: u64,
+    content_hash: String,
+    /// Output format and `--config` fingerprint this entry was converted
+    /// with (see `config::Config::fingerprint`); an entry only counts as
+    /// up to date for a rerun using the same settings.
+    format: String,
+    config_fingerprint: String,
+}
+
+/// Tracks which sources have already been converted, keyed by their
+/// output-relative path, so an interrupted or repeated batch run can
+/// skip unchanged inputs and resume cleanly. Backed by a
+/// newline-delimited JSON file in the output directory; each completion
+/// is appended and fsynced as it happens, so a killed run loses only the
+/// conversions still in flight.
+pub struct Manifest {
+    path: PathBuf,
+    entries: HashMap<PathBuf, ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn open(output_dir: &Path) -> io::Result<Manifest> {
+        let path = output_dir.join(".vdb_to_text_manifest.jsonl");
+        let mut entries = HashMap::new();
+
+        if let Ok(file) = File::open(&path) {
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if let Ok(entry) = serde_json::from_str::<ManifestEntry>(&line) {
+                    entries.insert(entry.key.clone(), entry);
+                }
+            }
+        } else {
+            std::fs::create_dir_all(output_dir)?;
+        }
+
+        Ok(Manifest { path, entries })
+    }
+
+    /// True if the source backing `key` hasn't changed (by mtime, size,
+    /// and a content hash) since it was last recorded as converted with
+    /// this same `format`/`config_fingerprint`. The content hash catches
+    /// an edit that happens to preserve both mtime and size; mtime/size
+    /// are still checked first so a changed file skips the hash's full
+    /// read in the common case. A rerun with a different `--format` or
+    /// `--config` never counts as up to date, even if the source file
+    /// itself is untouched.
+    pub fn is_up_to_date(
+        &self,
+        key: &Path,
+        disk_path: &Path,
+        format: &str,
+        config_fingerprint: &str,
+    ) -> bool {
+        let Some(entry) = self.entries.get(key) else {
+            return false;
+        };
+        if entry.format != format || entry.config_fingerprint != config_fingerprint {
+            return false;
+        }
+        let Ok(metadata) = std::fs::metadata(disk_path) else {
+            return false;
+        };
+        let Ok(mtime) = metadata.modified().and_then(to_unix_secs) else {
+            return false;
+        };
+        if entry.mtime_unix_secs != mtime || entry.size != metadata.len() {
+            return false;
+        }
+        let Ok(bytes) = std::fs::read(disk_path) else {
+            return false;
+        };
+        entry.content_hash == content_hash(&bytes)
+    }
+
+    /// Appends (and fsyncs) a completed conversion so a resumed run can
+    /// skip it.
+    pub fn record(
+        &mut self,
+        key: &Path,
+        disk_path: &Path,
+        format: &str,
+        config_fingerprint: &str,
+    ) -> io::Result<()> {
+        let metadata = std::fs::metadata(disk_path)?;
+        let bytes = std::fs::read(disk_path)?;
+        let entry = ManifestEntry {
+            key: key.to_path_buf(),
+            mtime_unix_secs: to_unix_secs(metadata.modified()?)?,
+            size: metadata.len(),
+            content_hash: content_hash(&bytes),
+            format: format.to_string(),
+            config_fingerprint: config_fingerprint.to_string(),
+        };
+
+        let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        file.sync_all()?;
+
+        self.entries.insert(entry.key.clone(), entry);
+        Ok(())
+    }
+}
+
+fn to_unix_secs(time: SystemTime) -> io::Result<u64> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .map_err(io::Error::other)
+}
+
+fn content_hash(bytes: &[u8]) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod is_up_to_date_tests {
+    use super::*;
+
+    fn fresh_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unknown_key_is_never_up_to_date() {
+        let dir = fresh_dir("vdb_to_text_test_manifest_unknown");
+        let source = dir.join("foo.vdb");
+        std::fs::write(&source, b"hello").unwrap();
+
+        let manifest = Manifest {
+            path: dir.join(".vdb_to_text_manifest.jsonl"),
+            entries: HashMap::new(),
+        };
+        assert!(!manifest.is_up_to_date(Path::new("foo.vdb"), &source, "csv", "abc"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unchanged_source_and_settings_is_up_to_date() {
+        let dir = fresh_dir("vdb_to_text_test_manifest_fresh");
+        let source = dir.join("foo.vdb");
+        std::fs::write(&source, b"hello").unwrap();
+
+        let mut manifest = Manifest {
+            path: dir.join(".vdb_to_text_manifest.jsonl"),
+            entries: HashMap::new(),
+        };
+        manifest
+            .record(Path::new("foo.vdb"), &source, "csv", "abc")
+            .unwrap();
+
+        assert!(manifest.is_up_to_date(Path::new("foo.vdb"), &source, "csv", "abc"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn different_format_or_config_is_not_up_to_date() {
+        let dir = fresh_dir("vdb_to_text_test_manifest_settings_change");
+        let source = dir.join("foo.vdb");
+        std::fs::write(&source, b"hello").unwrap();
+
+        let mut manifest = Manifest {
+            path: dir.join(".vdb_to_text_manifest.jsonl"),
+            entries: HashMap::new(),
+        };
+        manifest
+            .record(Path::new("foo.vdb"), &source, "csv", "abc")
+            .unwrap();
+
+        assert!(!manifest.is_up_to_date(Path::new("foo.vdb"), &source, "parquet", "abc"));
+        assert!(!manifest.is_up_to_date(Path::new("foo.vdb"), &source, "csv", "def"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn content_hash_catches_an_edit_that_preserves_mtime_and_size() {
+        let dir = fresh_dir("vdb_to_text_test_manifest_content_hash");
+        let source = dir.join("foo.vdb");
+        std::fs::write(&source, b"hello").unwrap();
+
+        let mut manifest = Manifest {
+            path: dir.join(".vdb_to_text_manifest.jsonl"),
+            entries: HashMap::new(),
+        };
+        manifest
+            .record(Path::new("foo.vdb"), &source, "csv", "abc")
+            .unwrap();
+
+        // Same length, different bytes, with mtime pinned back to what
+        // was recorded -- mtime/size alone would call this unchanged.
+        let recorded_mtime = std::fs::metadata(&source).unwrap().modified().unwrap();
+        std::fs::write(&source, b"jello").unwrap();
+        let file = File::options().write(true).open(&source).unwrap();
+        file.set_modified(recorded_mtime).unwrap();
+
+        assert!(!manifest.is_up_to_date(Path::new("foo.vdb"), &source, "csv", "abc"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
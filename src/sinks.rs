@@ -0,0 +1,413 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use csv::Writer as CsvWriter;
+use glam::IVec3;
+use half::f16;
+use parquet::{
+    data_type::{DoubleType, Int32Type},
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedFileWriter, SerializedRowGroupWriter},
+    },
+    schema::parser::parse_message_type,
+};
+
+/// A decoded voxel value, tagged with the `VoxelType` it was actually read
+/// as. Sinks must not narrow this to a single numeric type: `f16` can't
+/// represent the range a `u32`/`f32` grid is configured for (e.g.
+/// `f16::from_f32(100_000.0)` silently becomes `inf`), so each sink keeps
+/// enough precision to round-trip whatever type the user asked for.
+#[derive(Debug, Clone, Copy)]
+pub enum VoxelValue {
+    F16(f16),
+    F32(f32),
+    U32(u32),
+}
+
+impl VoxelValue {
+    /// Lossless widening to `f64`, for sinks (Parquet) whose column type
+    /// can't vary per grid. `f64`'s 52-bit mantissa exactly represents
+    /// every `f32` and every `u32`, so this never narrows.
+    fn as_f64(self) -> f64 {
+        match self {
+            VoxelValue::F16(v) => v.to_f64(),
+            VoxelValue::F32(v) => v as f64,
+            VoxelValue::U32(v) => v as f64,
+        }
+    }
+}
+
+impl std::fmt::Display for VoxelValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoxelValue::F16(v) => write!(f, "{}", v),
+            VoxelValue::F32(v) => write!(f, "{}", v),
+            VoxelValue::U32(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Destination for converted voxel records. Each implementation owns the
+/// on-disk layout for one output format; `parse_vdb_file` only talks to
+/// this trait, so adding a format never touches the VDB-reading side.
+pub trait VoxelSink {
+    fn write_header(&mut self) -> io::Result<()>;
+    fn write_voxel(&mut self, pos: IVec3, span: i32, value: VoxelValue) -> io::Result<()>;
+    fn finish(self: Box<Self>) -> io::Result<()>;
+}
+
+pub struct CsvSink {
+    writer: CsvWriter<File>,
+}
+
+impl CsvSink {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            writer: CsvWriter::from_path(path)?,
+        })
+    }
+}
+
+impl VoxelSink for CsvSink {
+    fn write_header(&mut self) -> io::Result<()> {
+        self.writer
+            .write_record(["x", "y", "z", "span", "value"])
+            .map_err(io::Error::other)
+    }
+
+    fn write_voxel(&mut self, pos: IVec3, span: i32, value: VoxelValue) -> io::Result<()> {
+        self.writer
+            .write_record([
+                pos.x.to_string(),
+                pos.y.to_string(),
+                pos.z.to_string(),
+                span.to_string(),
+                value.to_string(),
+            ])
+            .map_err(io::Error::other)
+    }
+
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Newline-delimited JSON: one `{"x":..,"y":..,"z":..,"span":..,"value":..}` per voxel.
+pub struct NdjsonSink {
+    writer: BufWriter<File>,
+}
+
+impl NdjsonSink {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+}
+
+impl VoxelSink for NdjsonSink {
+    fn write_header(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_voxel(&mut self, pos: IVec3, span: i32, value: VoxelValue) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            r#"{{"x":{},"y":{},"z":{},"span":{},"value":{}}}"#,
+            pos.x, pos.y, pos.z, span, value
+        )
+    }
+
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Raw little-endian `i32,i32,i32,i32,<value>` records, for fast reloading
+/// into NumPy/Pandas without a CSV parser in the loop. The trailing
+/// `value` field's width follows the grid's configured voxel type (2
+/// bytes for `f16`, 4 for `f32`/`u32`) rather than being fixed, since a
+/// config maps every voxel in one grid to the same type.
+pub struct BinSink {
+    writer: BufWriter<File>,
+}
+
+impl BinSink {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+}
+
+impl VoxelSink for BinSink {
+    fn write_header(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_voxel(&mut self, pos: IVec3, span: i32, value: VoxelValue) -> io::Result<()> {
+        self.writer.write_all(&pos.x.to_le_bytes())?;
+        self.writer.write_all(&pos.y.to_le_bytes())?;
+        self.writer.write_all(&pos.z.to_le_bytes())?;
+        self.writer.write_all(&span.to_le_bytes())?;
+        match value {
+            VoxelValue::F16(v) => self.writer.write_all(&v.to_le_bytes()),
+            VoxelValue::F32(v) => self.writer.write_all(&v.to_le_bytes()),
+            VoxelValue::U32(v) => self.writer.write_all(&v.to_le_bytes()),
+        }
+    }
+
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Columnar Parquet output. Voxels are buffered per-grid and written as a
+/// single row group on `finish`, since the parquet writer needs each
+/// column's full length up front. `value` is stored as `DOUBLE` rather
+/// than `FLOAT` so a `u32`/`f32`-configured grid doesn't lose precision.
+pub struct ParquetSink {
+    path: PathBuf,
+    x: Vec<i32>,
+    y: Vec<i32>,
+    z: Vec<i32>,
+    span: Vec<i32>,
+    value: Vec<f64>,
+}
+
+impl ParquetSink {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            x: Vec::new(),
+            y: Vec::new(),
+            z: Vec::new(),
+            span: Vec::new(),
+            value: Vec::new(),
+        })
+    }
+}
+
+impl VoxelSink for ParquetSink {
+    fn write_header(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_voxel(&mut self, pos: IVec3, span: i32, value: VoxelValue) -> io::Result<()> {
+        self.x.push(pos.x);
+        self.y.push(pos.y);
+        self.z.push(pos.z);
+        self.span.push(span);
+        self.value.push(value.as_f64());
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        let schema = parse_message_type(
+            "message voxel {
+                REQUIRED INT32 x;
+                REQUIRED INT32 y;
+                REQUIRED INT32 z;
+                REQUIRED INT32 span;
+                REQUIRED DOUBLE value;
+            }",
+        )
+        .map_err(io::Error::other)?;
+        let props = Arc::new(WriterProperties::builder().build());
+        let file = File::create(&self.path)?;
+        let mut writer =
+            SerializedFileWriter::new(file, Arc::new(schema), props).map_err(io::Error::other)?;
+        let mut row_group = writer.next_row_group().map_err(io::Error::other)?;
+
+        write_int32_column(&mut row_group, &self.x)?;
+        write_int32_column(&mut row_group, &self.y)?;
+        write_int32_column(&mut row_group, &self.z)?;
+        write_int32_column(&mut row_group, &self.span)?;
+        write_double_column(&mut row_group, &self.value)?;
+
+        row_group.close().map_err(io::Error::other)?;
+        writer.close().map_err(io::Error::other)?;
+        Ok(())
+    }
+}
+
+fn write_int32_column(
+    row_group: &mut SerializedRowGroupWriter<File>,
+    values: &[i32],
+) -> io::Result<()> {
+    let mut column = row_group
+        .next_column()
+        .map_err(io::Error::other)?
+        .ok_or_else(|| io::Error::other("parquet schema is missing a column"))?;
+    column
+        .typed::<Int32Type>()
+        .write_batch(values, None, None)
+        .map_err(io::Error::other)?;
+    column.close().map_err(io::Error::other)
+}
+
+fn write_double_column(
+    row_group: &mut SerializedRowGroupWriter<File>,
+    values: &[f64],
+) -> io::Result<()> {
+    let mut column = row_group
+        .next_column()
+        .map_err(io::Error::other)?
+        .ok_or_else(|| io::Error::other("parquet schema is missing a column"))?;
+    column
+        .typed::<DoubleType>()
+        .write_batch(values, None, None)
+        .map_err(io::Error::other)?;
+    column.close().map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::ivec3;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("vdb_to_text_test_sinks_{}", name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn csv_sink_writes_header_and_records() {
+        let path = temp_path("csv_sink.csv");
+        let mut sink = CsvSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        sink.write_voxel(ivec3(1, -2, 3), 4, VoxelValue::F16(f16::from_f32(0.5)))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "x,y,z,span,value\n1,-2,3,4,0.5\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn csv_sink_preserves_a_large_u32_value_instead_of_narrowing_to_f16() {
+        let path = temp_path("csv_sink_u32.csv");
+        let mut sink = CsvSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        // f16 tops out at 65504 and loses integer precision well before
+        // that; a u32 grid must not be routed through it.
+        sink.write_voxel(ivec3(0, 0, 0), 0, VoxelValue::U32(100_000))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "x,y,z,span,value\n0,0,0,0,100000\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn ndjson_sink_writes_one_json_object_per_voxel() {
+        let path = temp_path("ndjson_sink.jsonl");
+        let mut sink = NdjsonSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        sink.write_voxel(ivec3(1, -2, 3), 4, VoxelValue::F16(f16::from_f32(0.5)))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            "{\"x\":1,\"y\":-2,\"z\":3,\"span\":4,\"value\":0.5}\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn bin_sink_writes_little_endian_fields_in_order() {
+        let path = temp_path("bin_sink.bin");
+        let mut sink = BinSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        sink.write_voxel(ivec3(1, -2, 3), 4, VoxelValue::F16(f16::from_f32(0.5)))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let contents = std::fs::read(&path).unwrap();
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&1i32.to_le_bytes());
+        expected.extend_from_slice(&(-2i32).to_le_bytes());
+        expected.extend_from_slice(&3i32.to_le_bytes());
+        expected.extend_from_slice(&4i32.to_le_bytes());
+        expected.extend_from_slice(&f16::from_f32(0.5).to_le_bytes());
+        assert_eq!(contents, expected);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn bin_sink_writes_a_large_u32_value_at_full_width_instead_of_narrowing_to_f16() {
+        let path = temp_path("bin_sink_u32.bin");
+        let mut sink = BinSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        sink.write_voxel(ivec3(0, 0, 0), 0, VoxelValue::U32(100_000))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let contents = std::fs::read(&path).unwrap();
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&0i32.to_le_bytes());
+        expected.extend_from_slice(&0i32.to_le_bytes());
+        expected.extend_from_slice(&0i32.to_le_bytes());
+        expected.extend_from_slice(&0i32.to_le_bytes());
+        expected.extend_from_slice(&100_000u32.to_le_bytes());
+        assert_eq!(contents, expected);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parquet_sink_round_trips_the_written_voxel() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::RowAccessor;
+
+        let path = temp_path("parquet_sink.parquet");
+        let mut sink = ParquetSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        sink.write_voxel(ivec3(1, -2, 3), 4, VoxelValue::F16(f16::from_f32(0.5)))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let file = File::open(&path).unwrap();
+        let reader = SerializedFileReader::new(file).unwrap();
+        let mut rows = reader.get_row_iter(None).unwrap();
+        let row = rows.next().unwrap().unwrap();
+        assert_eq!(row.get_int(0).unwrap(), 1);
+        assert_eq!(row.get_int(1).unwrap(), -2);
+        assert_eq!(row.get_int(2).unwrap(), 3);
+        assert_eq!(row.get_int(3).unwrap(), 4);
+        assert_eq!(row.get_double(4).unwrap(), 0.5);
+        assert!(rows.next().is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parquet_sink_preserves_a_large_u32_value_instead_of_narrowing_to_f16() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::RowAccessor;
+
+        let path = temp_path("parquet_sink_u32.parquet");
+        let mut sink = ParquetSink::create(&path).unwrap();
+        sink.write_header().unwrap();
+        // f16::from_f32(100_000.0) is `inf`; the double column must carry
+        // the exact value through instead.
+        sink.write_voxel(ivec3(0, 0, 0), 0, VoxelValue::U32(100_000))
+            .unwrap();
+        Box::new(sink).finish().unwrap();
+
+        let file = File::open(&path).unwrap();
+        let reader = SerializedFileReader::new(file).unwrap();
+        let mut rows = reader.get_row_iter(None).unwrap();
+        let row = rows.next().unwrap().unwrap();
+        assert_eq!(row.get_double(4).unwrap(), 100_000.0);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
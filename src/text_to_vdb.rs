@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use glam::IVec3;
+use half::f16;
+
+/// One voxel record as emitted by `parse_vdb_file`'s CSV `VoxelSink`.
+#[derive(Debug)]
+struct VoxelRecord {
+    position: IVec3,
+    span: i32,
+    value: f16,
+}
+
+/// Groups a directory listing of exported CSVs by the base filename they
+/// came from (`foo.density.csv` and `foo.temperature.csv` both belong to
+/// `foo.vdb`), so each group can be rebuilt into a single multi-grid
+/// dump (see `reconstruct_vdb`).
+pub fn group_by_base_filename(csv_paths: Vec<PathBuf>) -> BTreeMap<PathBuf, Vec<PathBuf>> {
+    let mut groups: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for path in csv_paths {
+        if let Some(base) = base_output_path(&path) {
+            groups.entry(base).or_default().push(path);
+        }
+    }
+    groups
+}
+
+/// Merges the CSV records in `csv_paths` back into one file at
+/// `output_path`, grouped under a `grid <name>` line per source grid and
+/// restoring each voxel's recorded `span` (leaf vs. tile resolution)
+/// alongside its position and value.
+///
+/// This does **not** write an OpenVDB grid: `vdb-rs`, this project's only
+/// VDB dependency, is read-only in every published version -- there is no
+/// grid-builder or writer type to hand reconstructed voxels to. Rather
+/// than ship a `--reverse` that's guaranteed to fail on every invocation,
+/// this produces a plain-text intermediate dump of the validated,
+/// grouped records instead, so the merge/validate half of reversing a
+/// batch export is still useful pending a real VDB-writing dependency.
+pub fn reconstruct_vdb(csv_paths: &[PathBuf], output_path: &Path) -> io::Result<()> {
+    let write_dump = || -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(output_path)?);
+
+        for csv_path in csv_paths {
+            let grid_name = grid_name_from_csv(csv_path).ok_or_else(|| {
+                io::Error::other(format!("cannot infer grid name from {:?}", csv_path))
+            })?;
+            writeln!(out, "grid {}", grid_name)?;
+
+            let mut reader = csv::Reader::from_path(csv_path)?;
+            for record in reader.records() {
+                let voxel = parse_voxel_record(&record?)?;
+                writeln!(
+                    out,
+                    "{} {} {} {} {}",
+                    voxel.position.x, voxel.position.y, voxel.position.z, voxel.span, voxel.value
+                )?;
+            }
+        }
+
+        out.flush()
+    };
+
+    // Don't leave a partial dump behind when a record fails to parse.
+    let result = write_dump();
+    if result.is_err() {
+        let _ = std::fs::remove_file(output_path);
+    }
+    result
+}
+
+fn base_output_path(csv_path: &Path) -> Option<PathBuf> {
+    // "foo.density.csv" -> stem "foo.density" -> base "foo" -> "foo.vdb"
+    let stem = Path::new(csv_path.file_stem()?);
+    let mut base = stem.file_stem()?.to_os_string();
+    base.push(".vdb");
+    Some(csv_path.with_file_name(base))
+}
+
+fn grid_name_from_csv(csv_path: &Path) -> Option<String> {
+    let stem = csv_path.file_stem()?.to_str()?; // "foo.density"
+    let (_, grid_name) = stem.rsplit_once('.')?;
+    Some(grid_name.to_string())
+}
+
+fn parse_voxel_record(record: &csv::StringRecord) -> io::Result<VoxelRecord> {
+    let field = |i: usize| -> io::Result<&str> {
+        record
+            .get(i)
+            .ok_or_else(|| io::Error::other("voxel record is missing a field"))
+    };
+    let parse_i32 = |i: usize| -> io::Result<i32> {
+        field(i)?
+            .parse()
+            .map_err(|_| io::Error::other("voxel record has a non-numeric field"))
+    };
+
+    let x = parse_i32(0)?;
+    let y = parse_i32(1)?;
+    let z = parse_i32(2)?;
+    let span = parse_i32(3)?;
+    let value: f32 = field(4)?
+        .parse()
+        .map_err(|_| io::Error::other("voxel record has a non-numeric value"))?;
+
+    Ok(VoxelRecord {
+        position: IVec3::new(x, y, z),
+        span,
+        value: f16::from_f32(value),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("vdb_to_text_test_text_to_vdb_{}", name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn reconstruct_vdb_merges_grouped_csvs_into_one_grid_prefixed_dump() {
+        let density_csv = temp_path("reconstruct.density.csv");
+        std::fs::write(&density_csv, "x,y,z,span,value\n1,-2,3,4,0.5\n").unwrap();
+        let output = temp_path("reconstruct_output.vdb");
+
+        reconstruct_vdb(&[density_csv.clone()], &output).unwrap();
+
+        let contents = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(contents, "grid density\n1 -2 3 4 0.5\n");
+
+        std::fs::remove_file(&density_csv).unwrap();
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    #[test]
+    fn reconstruct_vdb_rejects_a_malformed_record_instead_of_writing_a_partial_dump() {
+        let csv_path = temp_path("reconstruct.malformed.csv");
+        std::fs::write(&csv_path, "x,y,z,span,value\nnot-a-number,-2,3,4,0.5\n").unwrap();
+        let output = temp_path("reconstruct_malformed_output.vdb");
+
+        assert!(reconstruct_vdb(&[csv_path.clone()], &output).is_err());
+        assert!(!output.exists());
+
+        std::fs::remove_file(&csv_path).unwrap();
+    }
+
+    #[test]
+    fn groups_sibling_grid_csvs_under_one_base_vdb() {
+        let csv_paths = vec![
+            PathBuf::from("out/foo.density.csv"),
+            PathBuf::from("out/foo.temperature.csv"),
+            PathBuf::from("out/bar.density.csv"),
+        ];
+        let groups = group_by_base_filename(csv_paths);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&PathBuf::from("out/foo.vdb")].len(), 2);
+        assert_eq!(groups[&PathBuf::from("out/bar.vdb")].len(), 1);
+    }
+
+    #[test]
+    fn grid_name_from_csv_reads_the_segment_before_the_extension() {
+        assert_eq!(
+            grid_name_from_csv(Path::new("out/foo.density.csv")),
+            Some("density".to_string())
+        );
+        assert_eq!(grid_name_from_csv(Path::new("out/foo.csv")), None);
+    }
+
+    #[test]
+    fn parse_voxel_record_reads_position_span_and_value() {
+        let record = csv::StringRecord::from(vec!["1", "-2", "3", "4", "0.5"]);
+        let voxel = parse_voxel_record(&record).unwrap();
+        assert_eq!(voxel.position, IVec3::new(1, -2, 3));
+        assert_eq!(voxel.span, 4);
+        assert_eq!(voxel.value, f16::from_f32(0.5));
+    }
+
+    #[test]
+    fn parse_voxel_record_rejects_non_numeric_fields() {
+        let record = csv::StringRecord::from(vec!["x", "-2", "3", "4", "0.5"]);
+        assert!(parse_voxel_record(&record).is_err());
+    }
+}